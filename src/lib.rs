@@ -1,7 +1,9 @@
 //! # Hardware-based tick counters for high-precision benchmarks
 //! * `x86_64`  - executes [RDTSC](https://www.intel.com/content/dam/www/public/us/en/documents/white-papers/ia-32-ia-64-benchmark-code-execution-paper.pdf) CPU instruction to read the time-stamp counter.
 //! * `AArch64` - reads value of the [CNTVCT_EL0](https://developer.arm.com/documentation/ddi0595/2021-12/AArch64-Registers/CNTVCT-EL0--Counter-timer-Virtual-Count-register) counter-timer register.
-//! 
+//! * Other architectures - falls back to `QueryPerformanceCounter` on Windows, or to
+//!   [`std::time::Instant`] elsewhere, so the API still compiles and runs.
+//!
 //! ## Basic usage
 //! 
 //!```
@@ -11,7 +13,7 @@
 //! println!("Number of elapsed ticks: {}", elapsed_ticks);
 //!```
 
-use std::{time::Duration, arch::asm};
+use std::{time::{Duration, Instant}, arch::asm};
 
 /// The origin of the provided counter frequency
 pub enum TickCounterFrequencyBase {
@@ -19,7 +21,13 @@ pub enum TickCounterFrequencyBase {
     Hardware,
 
     /// Frequency is measured by counting number of ticks in `Duration` of time
-    Measured(Duration)
+    Measured(Duration),
+
+    /// Frequency is read directly from CPUID leaves on invariant-TSC hardware
+    Nominal,
+
+    /// Frequency is a fixed software value, used on architectures without a native tick counter
+    Software
 }
 
 /// Returns a current value of tick counter on `aarch64` architecture
@@ -68,14 +76,76 @@ pub fn frequency() -> (u64, TickCounterFrequencyBase) {
 
 /// Returns a frequency of tick counter in hertz (Hz)
 /// * Returns a hardware-provided value of tick counter frequency on `aarch64` architecture.
-/// * Returns a software-measured value of tick counter frequency on `x86_64` architecture measured in 1 second.
+/// * On `x86_64`, prefers the nominal frequency read from CPUID on invariant-TSC hardware,
+///   falling back to a software-measured value over 1 second when CPUID doesn't provide one.
 #[cfg(target_arch = "x86_64")]
 pub fn frequency() -> (u64, TickCounterFrequencyBase)  {
+    if let Some(nominal_frequency) = x86_64_cpuid_frequency() {
+        return (nominal_frequency, TickCounterFrequencyBase::Nominal);
+    }
+
     let measure_duration = Duration::from_secs(1);
     let frequency_base = TickCounterFrequencyBase::Measured(measure_duration);
     (x86_64_measure_frequency(&measure_duration), frequency_base)
 }
 
+/// Returns `true` when the CPU reports an invariant TSC via CPUID leaf `0x80000007`
+/// (`EDX` bit 8), meaning the counter ticks at a constant rate regardless of power
+/// state changes and is therefore safe to use for the CPUID-derived frequency below.
+#[cfg(target_arch = "x86_64")]
+pub fn x86_64_invariant_tsc() -> bool {
+    use core::arch::x86_64::{__cpuid, __get_cpuid_max};
+
+    let (max_extended_leaf, _) = __get_cpuid_max(0x8000_0000);
+    if max_extended_leaf < 0x8000_0007 {
+        return false;
+    }
+
+    let leaf = __cpuid(0x8000_0007);
+    (leaf.edx & (1 << 8)) != 0
+}
+
+/// Attempts to read the nominal TSC frequency directly from CPUID, avoiding the
+/// one-second delay of [`x86_64_measure_frequency`].
+///
+/// Reads leaf `0x15` (`EAX` = TSC/crystal ratio denominator, `EBX` = numerator,
+/// `ECX` = core crystal clock in Hz); if `ECX` is zero, falls back to leaf `0x16`'s
+/// `EAX`, the processor base frequency in MHz. Returns `None` when the CPU doesn't
+/// report an invariant TSC, or when none of these leaves yield usable values.
+#[cfg(target_arch = "x86_64")]
+pub fn x86_64_cpuid_frequency() -> Option<u64> {
+    use core::arch::x86_64::{__cpuid, __get_cpuid_max};
+
+    if !x86_64_invariant_tsc() {
+        return None;
+    }
+
+    let (max_leaf, _) = __get_cpuid_max(0);
+    if max_leaf < 0x15 {
+        return None;
+    }
+
+    let leaf15 = __cpuid(0x15);
+    let (denominator, numerator) = (leaf15.eax, leaf15.ebx);
+    let crystal_hz = leaf15.ecx as u64;
+    if crystal_hz == 0 {
+        if max_leaf < 0x16 {
+            return None;
+        }
+        let leaf16 = __cpuid(0x16);
+        if leaf16.eax == 0 {
+            return None;
+        }
+        return Some(leaf16.eax as u64 * 1_000_000);
+    }
+
+    if denominator == 0 || numerator == 0 {
+        return None;
+    }
+
+    Some(crystal_hz * (numerator as u64) / (denominator as u64))
+}
+
 /// Returns a current value of the tick counter based on Intel CPU's `RDTSC` instruction
 /// 
 /// This function is an aternative to Rust's core functions:
@@ -113,6 +183,79 @@ pub fn x86_64_processor_id() -> (u64, u32) {
     ((reg_edx as u64) << 32 | reg_eax as u64, reg_ecx)
 }
 
+/// Returns `(tick counter, processor id)` to use as the staring point of a [`x86_64_guarded_elapsed`]
+/// measurement, serialized with the same `mfence`/`lfence` pair as [`start`]
+#[cfg(target_arch = "x86_64")]
+#[inline]
+pub fn x86_64_guarded_start() -> (u64, u32) {
+    let rax: u64;
+    let ecx: u32;
+    unsafe {
+        asm!(
+            "mfence",
+            "lfence",
+            "rdtscp",
+            "shl rdx, 32",
+            "or rax, rdx",
+            out("rax") rax,
+            out("ecx") ecx
+        );
+    }
+    (rax, ecx)
+}
+
+/// Returns `(tick counter, processor id)` to use as the stopping point of a [`x86_64_guarded_elapsed`]
+/// measurement, serialized with the same `lfence` as [`stop`]
+#[cfg(target_arch = "x86_64")]
+#[inline]
+pub fn x86_64_guarded_stop() -> (u64, u32) {
+    let rax: u64;
+    let ecx: u32;
+    unsafe {
+        asm!(
+            "rdtscp",
+            "lfence",
+            "shl rdx, 32",
+            "or rax, rdx",
+            out("rax") rax,
+            out("ecx") ecx
+        );
+    }
+    (rax, ecx)
+}
+
+/// Signals that the thread migrated between processor cores between a [`x86_64_guarded_start`]
+/// and a [`x86_64_guarded_stop`], making the raw tick delta potentially invalid on hardware that
+/// doesn't synchronize the TSC across cores
+#[derive(Debug, PartialEq, Eq)]
+pub struct CoreMigrated {
+    /// Processor id observed at the starting point
+    pub start_processor_id: u32,
+
+    /// Processor id observed at the stopping point
+    pub stop_processor_id: u32
+}
+
+/// Returns the elapsed ticks between a [`x86_64_guarded_start`] and a [`x86_64_guarded_stop`] pair, or
+/// `Err` if the processor id differs between them, meaning the thread migrated cores and
+/// the tick delta shouldn't be trusted
+///
+/// # Arguments
+///
+/// * `start` - A `(tick counter, processor id)` pair captured with [`x86_64_guarded_start`]
+/// * `stop` - A `(tick counter, processor id)` pair captured with [`x86_64_guarded_stop`]
+#[cfg(target_arch = "x86_64")]
+pub fn x86_64_guarded_elapsed(start: (u64, u32), stop: (u64, u32)) -> Result<u64, CoreMigrated> {
+    let (start_ticks, start_processor_id) = start;
+    let (stop_ticks, stop_processor_id) = stop;
+
+    if start_processor_id != stop_processor_id {
+        return Err(CoreMigrated { start_processor_id, stop_processor_id });
+    }
+
+    Ok(stop_ticks - start_ticks)
+}
+
 /// Returns a current value of the tick counter to use as a staring point
 #[cfg(target_arch = "x86_64")]
 #[inline]
@@ -162,11 +305,333 @@ pub fn x86_64_measure_frequency(measure_duration: &Duration) -> u64 {
     (((counter_stop - counter_start) as f64) / measure_duration.as_secs_f64()) as u64
 }
 
+/// Returns a current value of the tick counter to use as a staring point, backed by
+/// `QueryPerformanceCounter` on architectures without a native tick counter
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), target_os = "windows"))]
+#[inline]
+pub fn start() -> u64 {
+    let mut counter: i64 = 0;
+    unsafe { QueryPerformanceCounter(&mut counter) };
+    counter as u64
+}
+
+/// Returns a current value of the tick counter to use as a stopping point, backed by
+/// `QueryPerformanceCounter` on architectures without a native tick counter
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), target_os = "windows"))]
+#[inline]
+pub fn stop() -> u64 {
+    start()
+}
+
+/// Returns a frequency of tick counter in hertz (Hz), read from `QueryPerformanceFrequency`
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), target_os = "windows"))]
+pub fn frequency() -> (u64, TickCounterFrequencyBase) {
+    let mut counter_frequency: i64 = 0;
+    unsafe { QueryPerformanceFrequency(&mut counter_frequency) };
+    (counter_frequency as u64, TickCounterFrequencyBase::Hardware)
+}
+
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), target_os = "windows"))]
+extern "system" {
+    fn QueryPerformanceCounter(counter: *mut i64) -> i32;
+    fn QueryPerformanceFrequency(frequency: *mut i64) -> i32;
+}
+
+/// Returns the process-wide epoch used by the [`std::time::Instant`]-backed fallback
+/// counter, lazily pinned on first use
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), not(target_os = "windows")))]
+fn software_epoch() -> &'static Instant {
+    use std::sync::OnceLock;
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now)
+}
+
+/// Returns a current value of the tick counter to use as a staring point, backed by
+/// [`std::time::Instant`] on architectures without a native tick counter
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), not(target_os = "windows")))]
+#[inline]
+pub fn start() -> u64 {
+    software_epoch().elapsed().as_nanos() as u64
+}
+
+/// Returns a current value of the tick counter to use as a stopping point, backed by
+/// [`std::time::Instant`] on architectures without a native tick counter
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), not(target_os = "windows")))]
+#[inline]
+pub fn stop() -> u64 {
+    software_epoch().elapsed().as_nanos() as u64
+}
+
+/// Returns a frequency of tick counter in hertz (Hz)
+/// Returns a fixed 1 GHz value, since the "tick" here is simply nanoseconds elapsed
+/// since an internal epoch
+#[cfg(all(not(any(target_arch = "x86_64", target_arch = "aarch64")), not(target_os = "windows")))]
+pub fn frequency() -> (u64, TickCounterFrequencyBase) {
+    (1_000_000_000, TickCounterFrequencyBase::Software)
+}
+
 /// Returns a precision of tick counters in nanoseconds
 pub fn precision(frequency: u64) -> f64{
     1.0e9_f64 / (frequency as f64)
 }
 
+/// Number of tick samples collected per round of adaptive sampling in [`benchmark`]
+const BENCHMARK_BATCH_SIZE: usize = 32;
+
+/// Upper bound on the number of samples [`benchmark`] will collect before giving up
+/// on reaching the target relative MAD
+const BENCHMARK_MAX_SAMPLES: usize = 100_000;
+
+/// Sampling stops once the MAD falls to within this fraction of the median
+const BENCHMARK_RELATIVE_MAD_THRESHOLD: f64 = 0.02;
+
+/// Robust timing statistics produced by [`benchmark`]
+///
+/// The median and median absolute deviation (MAD) are used instead of the
+/// arithmetic mean and standard deviation, since they are far more resistant to
+/// the occasional large outliers caused by interrupts or scheduler preemption.
+pub struct BenchmarkStatistics {
+    /// Median number of elapsed ticks per call, with measurement overhead subtracted
+    pub median_ticks: u64,
+
+    /// Median elapsed time per call in nanoseconds, with measurement overhead subtracted
+    pub median_nanoseconds: f64,
+
+    /// Median absolute deviation of the raw tick samples
+    pub mad_ticks: u64,
+
+    /// Minimum number of elapsed ticks observed, with measurement overhead subtracted
+    pub min_ticks: u64,
+
+    /// Total number of samples collected
+    pub samples: usize
+}
+
+/// Returns the median of `samples`, sorting it in place
+fn median(samples: &mut [u64]) -> u64 {
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+/// Returns the median absolute deviation of `samples` around `median_value`
+fn median_absolute_deviation(samples: &[u64], median_value: u64) -> u64 {
+    let mut deviations: Vec<u64> = samples.iter().map(|&sample| sample.abs_diff(median_value)).collect();
+    median(&mut deviations)
+}
+
+/// Repeatedly collects batches of `BENCHMARK_BATCH_SIZE` samples from `sample` until
+/// the relative MAD drops to [`BENCHMARK_RELATIVE_MAD_THRESHOLD`] or the sample count
+/// reaches [`BENCHMARK_MAX_SAMPLES`]
+///
+/// Convergence is only re-checked at a doubling sample count (32, 64, 128, ...) rather
+/// than after every batch, so the total cost of re-sorting stays `O(n log n)` instead of
+/// quadratic in the worst case of a distribution (e.g. genuinely bimodal timings) whose
+/// MAD never converges and that would otherwise re-sort the whole growing sample set on
+/// every single batch.
+fn collect_until_stable<F: FnMut() -> u64>(mut sample: F) -> Vec<u64> {
+    let mut samples = Vec::with_capacity(BENCHMARK_BATCH_SIZE);
+    let mut next_check = BENCHMARK_BATCH_SIZE;
+
+    loop {
+        for _ in 0..BENCHMARK_BATCH_SIZE {
+            samples.push(sample());
+        }
+
+        if samples.len() >= next_check {
+            let median_value = median(&mut samples);
+            let mad_value = median_absolute_deviation(&samples, median_value) as f64;
+            let relative_mad = if median_value == 0 { 0.0 } else { mad_value / median_value as f64 };
+
+            if relative_mad <= BENCHMARK_RELATIVE_MAD_THRESHOLD {
+                break;
+            }
+
+            next_check = next_check.saturating_mul(2);
+        }
+
+        if samples.len() >= BENCHMARK_MAX_SAMPLES {
+            break;
+        }
+    }
+    samples
+}
+
+/// Benchmarks `f` by repeatedly timing it and returns robust statistics instead of
+/// the naive mean/stddev
+///
+/// Samples are collected in batches; the median and median absolute deviation (MAD)
+/// are recomputed at a doubling sample count, and sampling continues until the MAD
+/// falls to within a small relative threshold of the median or a maximum sample
+/// count is reached. Before measuring `f`, the per-call measurement overhead is
+/// estimated the same way by timing an empty region, and subtracted from the result.
+///
+/// # Arguments
+///
+/// * `f` - The closure to benchmark
+pub fn benchmark<F: FnMut()>(mut f: F) -> BenchmarkStatistics {
+    let overhead_samples = collect_until_stable(|| {
+        let counter_start = start();
+        stop() - counter_start
+    });
+    let overhead_median = median(&mut overhead_samples.clone());
+
+    let samples = collect_until_stable(|| {
+        let counter_start = start();
+        f();
+        stop() - counter_start
+    });
+
+    let median_raw = median(&mut samples.clone());
+    let mad_ticks = median_absolute_deviation(&samples, median_raw);
+    let median_ticks = median_raw.saturating_sub(overhead_median);
+    let min_ticks = samples.iter().min().copied().unwrap_or(0).saturating_sub(overhead_median);
+
+    let (counter_frequency, _) = frequency();
+    let median_nanoseconds = median_ticks as f64 * precision(counter_frequency);
+
+    BenchmarkStatistics {
+        median_ticks,
+        median_nanoseconds,
+        mad_ticks,
+        min_ticks,
+        samples: samples.len()
+    }
+}
+
+/// The exponential moving average weight given to a freshly observed frequency in
+/// [`Clocksource::recalibrate`]
+const CLOCKSOURCE_RECALIBRATION_EMA_ALPHA: f64 = 0.1;
+
+/// A calibrated monotonic clock backed by the hardware tick counter
+///
+/// Holds a reference epoch captured from both [`std::time::Instant`] and [`start`] at
+/// construction time, plus the measured tick frequency, so [`now_nanos`](Clocksource::now_nanos)
+/// can turn a single tick-counter read into elapsed nanoseconds without a syscall.
+pub struct Clocksource {
+    epoch_instant: Instant,
+    epoch_ticks: u64,
+    frequency: f64
+}
+
+impl Clocksource {
+    /// Creates a new `Clocksource`, pinning its epoch to the current instant and measuring
+    /// the tick counter frequency
+    pub fn new() -> Self {
+        let (counter_frequency, _) = frequency();
+        Clocksource {
+            epoch_instant: Instant::now(),
+            epoch_ticks: start(),
+            frequency: counter_frequency as f64
+        }
+    }
+
+    /// Returns the number of nanoseconds elapsed since the epoch, computed as
+    /// `(stop() - epoch_ticks) * 1e9 / frequency`
+    ///
+    /// Uses a saturating subtraction: on hardware without a cross-core-synchronized TSC,
+    /// a thread migration between the epoch and this read can make `stop()` appear to be
+    /// before `epoch_ticks`, which would otherwise underflow. When that happens this
+    /// returns `0` rather than a bogus near-`u64::MAX` value; callers on x86_64 needing a
+    /// hard guarantee against migration should use `x86_64_guarded_elapsed` directly.
+    pub fn now_nanos(&self) -> u64 {
+        let elapsed_ticks = stop().saturating_sub(self.epoch_ticks);
+        (elapsed_ticks as f64 * 1.0e9_f64 / self.frequency) as u64
+    }
+
+    /// Re-pins the epoch against `Instant::now()` and updates the tracked frequency using
+    /// an exponential moving average of the newly observed frequency, so long-running
+    /// processes track TSC drift instead of accumulating error
+    ///
+    /// Uses the same saturating subtraction as [`now_nanos`](Clocksource::now_nanos) to
+    /// stay safe across a migrated-core read; a saturated (zero) reading simply skips
+    /// the frequency update for this call instead of corrupting the EMA.
+    pub fn recalibrate(&mut self) {
+        let now_instant = Instant::now();
+        let now_ticks = stop();
+
+        let elapsed_ticks = now_ticks.saturating_sub(self.epoch_ticks);
+        let elapsed_seconds = (now_instant - self.epoch_instant).as_secs_f64();
+        if elapsed_ticks > 0 && elapsed_seconds > 0.0 {
+            let observed_frequency = elapsed_ticks as f64 / elapsed_seconds;
+            self.frequency = CLOCKSOURCE_RECALIBRATION_EMA_ALPHA * observed_frequency
+                + (1.0 - CLOCKSOURCE_RECALIBRATION_EMA_ALPHA) * self.frequency;
+        }
+
+        self.epoch_instant = now_instant;
+        self.epoch_ticks = now_ticks;
+    }
+}
+
+impl Default for Clocksource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Issues a CPU relax hint to reduce power draw and pipeline pressure inside a busy-wait loop
+#[cfg(target_arch = "x86_64")]
+#[inline]
+fn relax() {
+    unsafe {
+        asm!("pause");
+    }
+}
+
+/// Issues a CPU relax hint to reduce power draw and pipeline pressure inside a busy-wait loop
+#[cfg(target_arch = "aarch64")]
+#[inline]
+fn relax() {
+    unsafe {
+        asm!("yield");
+    }
+}
+
+/// Issues a CPU relax hint to reduce power draw and pipeline pressure inside a busy-wait loop
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+#[inline]
+fn relax() {
+    std::hint::spin_loop();
+}
+
+/// Busy-waits until the tick counter reaches `deadline_ticks` (as returned by [`start`]
+/// or [`stop`]), issuing a relax hint on each iteration
+///
+/// This is the standard technique used by emulators and drivers that need calibrated
+/// busy-waits tighter than the OS scheduler quantum.
+///
+/// # Arguments
+///
+/// * `deadline_ticks` - The tick counter value to wait for
+pub fn spin_until(deadline_ticks: u64) {
+    while stop() < deadline_ticks {
+        relax();
+    }
+}
+
+/// Returns the tick counter frequency, measuring it via [`frequency`] only on first use
+///
+/// [`frequency`] can fall back to a one-second measurement on hardware that doesn't
+/// expose it directly (e.g. an x86_64 CPU without invariant-TSC CPUID leaves), which
+/// would otherwise make every [`spin_for`] call block for a full second just to
+/// re-derive a value that never changes for the lifetime of the process.
+fn cached_frequency() -> u64 {
+    use std::sync::OnceLock;
+    static FREQUENCY: OnceLock<u64> = OnceLock::new();
+    *FREQUENCY.get_or_init(|| frequency().0)
+}
+
+/// Busy-waits until at least `duration` has elapsed, converting it to ticks using the
+/// cached tick counter frequency and then delegating to [`spin_until`]
+///
+/// # Arguments
+///
+/// * `duration` - The minimum amount of time to busy-wait for
+pub fn spin_for(duration: Duration) {
+    let ticks = (duration.as_secs_f64() * cached_frequency() as f64) as u64;
+    let deadline_ticks = start() + ticks;
+    spin_until(deadline_ticks);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,11 +712,22 @@ mod tests {
     fn test_x86_64_counter_frequency() {
         let (counter_frequency, frequency_base) = frequency();
         assert!(counter_frequency > 0);
-        let estimated_duration = match frequency_base {
-            TickCounterFrequencyBase::Hardware => None,
-            TickCounterFrequencyBase::Measured(duration) => Some(duration)
-        };
-        assert_eq!(estimated_duration, Some(Duration::from_millis(1000)));
+        match frequency_base {
+            TickCounterFrequencyBase::Hardware | TickCounterFrequencyBase::Software => panic!("Unexpected frequency base!"),
+            TickCounterFrequencyBase::Nominal => (),
+            TickCounterFrequencyBase::Measured(duration) => assert_eq!(duration, Duration::from_millis(1000))
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_x86_64_cpuid_frequency() {
+        if x86_64_invariant_tsc() {
+            let cpuid_frequency = x86_64_cpuid_frequency();
+            assert!(cpuid_frequency.is_none() || cpuid_frequency.unwrap() > 0);
+        } else {
+            assert!(x86_64_cpuid_frequency().is_none());
+        }
     }
 
     #[test]
@@ -272,4 +748,61 @@ mod tests {
         let counter_accuracy = precision(counter_frequency);
         assert_eq!((counter_accuracy as u64), 41);
     }
+
+    #[test]
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+    fn test_benchmark() {
+        let statistics = benchmark(|| {
+            let mut sum: u64 = 0;
+            for i in 0..100 {
+                sum = sum.wrapping_add(i);
+            }
+            std::hint::black_box(sum);
+        });
+
+        assert!(statistics.samples > 0);
+        assert!(statistics.median_nanoseconds >= 0.0);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+    fn test_clocksource() {
+        use std::{thread, time};
+
+        let mut clocksource = Clocksource::new();
+        thread::sleep(time::Duration::from_millis(20));
+        let elapsed_nanos = clocksource.now_nanos();
+        assert!(elapsed_nanos >= time::Duration::from_millis(10).as_nanos() as u64);
+
+        clocksource.recalibrate();
+        thread::sleep(time::Duration::from_millis(5));
+        assert!(clocksource.now_nanos() > 0);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_x86_64_guarded_elapsed() {
+        let guarded_start = x86_64_guarded_start();
+        let guarded_stop = x86_64_guarded_stop();
+
+        let elapsed_ticks = x86_64_guarded_elapsed(guarded_start, guarded_stop);
+        assert!(elapsed_ticks.is_ok());
+        assert!(elapsed_ticks.unwrap() > 0);
+
+        let migrated = x86_64_guarded_elapsed((0, 1), (0, 2));
+        assert_eq!(migrated, Err(CoreMigrated { start_processor_id: 1, stop_processor_id: 2 }));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+    fn test_spin_for() {
+        let duration = Duration::from_millis(10);
+        let counter_start = start();
+        spin_for(duration);
+        let elapsed_ticks = stop() - counter_start;
+
+        let (counter_frequency, _) = frequency();
+        let elapsed_nanoseconds = elapsed_ticks as f64 * precision(counter_frequency);
+        assert!(elapsed_nanoseconds >= duration.as_nanos() as f64);
+    }
 }